@@ -1,31 +1,237 @@
 //! Controller Area Network
 
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+/// Payload of a classic CAN data or remote frame.
+///
+/// Wraps a fixed 8 byte buffer together with the payload's length, so a
+/// frame's data can be carried by value, compared and built without an
+/// allocator, and without each [`Frame`] implementation re-checking the
+/// length bound.
+#[derive(Debug, Copy, Clone, Eq)]
+pub struct Data {
+    bytes: [u8; 8],
+    len: u8,
+}
+
+impl Data {
+    /// Creates an empty payload.
+    pub fn empty() -> Self {
+        Self {
+            bytes: [0; 8],
+            len: 0,
+        }
+    }
+}
+
+impl Deref for Data {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl AsRef<[u8]> for Data {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl PartialEq for Data {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl PartialEq<[u8]> for Data {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl TryFrom<&[u8]> for Data {
+    type Error = ();
+
+    /// Copies `slice` into a `Data`.
+    ///
+    /// Returns an error when `slice` is longer than 8 bytes.
+    fn try_from(slice: &[u8]) -> Result<Self, ()> {
+        if slice.len() > 8 {
+            return Err(());
+        }
+
+        let mut bytes = [0; 8];
+        bytes[..slice.len()].copy_from_slice(slice);
+        Ok(Self {
+            bytes,
+            len: slice.len() as u8,
+        })
+    }
+}
+
+macro_rules! data_from_array {
+    ($($n:expr),*) => {
+        $(
+            impl From<[u8; $n]> for Data {
+                fn from(array: [u8; $n]) -> Self {
+                    let mut bytes = [0; 8];
+                    bytes[..$n].copy_from_slice(&array);
+                    Self { bytes, len: $n }
+                }
+            }
+        )*
+    };
+}
+
+data_from_array!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+
+/// Standard 11bit Identifier (`0..=0x7FF`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct StandardId(u16);
+
+impl StandardId {
+    /// CAN ID `0`, the highest priority standard identifier.
+    pub const ZERO: Self = Self(0);
+
+    /// CAN ID `0x7FF`, the lowest priority standard identifier.
+    pub const MAX: Self = Self(0x7FF);
+
+    /// Creates a new `StandardId`.
+    ///
+    /// Returns `None` if `raw` is out of range (`0..=0x7FF`).
+    pub fn new(raw: u16) -> Option<Self> {
+        if raw <= 0x7FF {
+            Some(Self(raw))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new `StandardId` without checking that `raw` is in range.
+    ///
+    /// Using an out-of-range value is not memory unsafe, but may cause
+    /// implementations to emit invalid frames.
+    pub fn new_unchecked(raw: u16) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw identifier value.
+    pub fn as_raw(&self) -> u16 {
+        self.0
+    }
+}
+
+/// Extended 29bit Identifier (`0..=0x1FFF_FFFF`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct ExtendedId(u32);
+
+impl ExtendedId {
+    /// CAN ID `0`, the highest priority extended identifier.
+    pub const ZERO: Self = Self(0);
+
+    /// CAN ID `0x1FFF_FFFF`, the lowest priority extended identifier.
+    pub const MAX: Self = Self(0x1FFF_FFFF);
+
+    /// Creates a new `ExtendedId`.
+    ///
+    /// Returns `None` if `raw` is out of range (`0..=0x1FFF_FFFF`).
+    pub fn new(raw: u32) -> Option<Self> {
+        if raw <= 0x1FFF_FFFF {
+            Some(Self(raw))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new `ExtendedId` without checking that `raw` is in range.
+    ///
+    /// Using an out-of-range value is not memory unsafe, but may cause
+    /// implementations to emit invalid frames.
+    pub fn new_unchecked(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw identifier value.
+    pub fn as_raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the base `StandardId` that shares the same top 11 bits.
+    ///
+    /// This is the identifier used for arbitration against standard frames
+    /// carrying the same base identifier.
+    pub fn standard_id(&self) -> StandardId {
+        StandardId((self.0 >> 18) as u16)
+    }
+}
+
 /// CAN Identifier
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Id {
     /// Standard 11bit Identifier (0..=0x7FF)
-    Standard(u32),
+    Standard(StandardId),
 
     /// Extended 29bit Identifier (0..=0x1FFF_FFFF)
-    Extended(u32),
+    Extended(ExtendedId),
 }
 
-impl Id {
-    /// Returs true when the identifier is valid, false otherwise.
-    pub fn valid(self) -> bool {
-        match self {
-            Id::Standard(id) if id <= 0x7FF => true,
-            Id::Extended(id) if id <= 0x1FFF_FFFF => true,
-            _ => false,
-        }
+impl From<StandardId> for Id {
+    fn from(id: StandardId) -> Self {
+        Id::Standard(id)
+    }
+}
+
+impl From<ExtendedId> for Id {
+    fn from(id: ExtendedId) -> Self {
+        Id::Extended(id)
+    }
+}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Id {
+    /// Orders identifiers by CAN arbitration priority: a lower identifier
+    /// compares as having higher priority.
+    ///
+    /// Arbitration is decided on the raw bits driven onto the bus, which for
+    /// an extended frame start with the same 11 bits as its base standard
+    /// identifier. When both identifiers share that base, the standard frame
+    /// wins arbitration because its next bit (RTR) is dominant compared to
+    /// the extended frame's recessive SRR/IDE bits.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let self_base = match self {
+            Id::Standard(id) => *id,
+            Id::Extended(id) => id.standard_id(),
+        };
+        let other_base = match other {
+            Id::Standard(id) => *id,
+            Id::Extended(id) => id.standard_id(),
+        };
+
+        self_base.cmp(&other_base).then_with(|| match (self, other) {
+            (Id::Standard(_), Id::Extended(_)) => Ordering::Less,
+            (Id::Extended(_), Id::Standard(_)) => Ordering::Greater,
+            (Id::Standard(_), Id::Standard(_)) => Ordering::Equal,
+            (Id::Extended(a), Id::Extended(b)) => a.as_raw().cmp(&b.as_raw()),
+        })
     }
 }
 
 /// A CAN2.0 Frame
 pub trait Frame: Sized {
     /// Creates a new frame.
-    /// Returns an error when the the identifier is not valid or the data slice is too long.
-    fn new(id: Id, data: &[u8]) -> Result<Self, ()>;
+    ///
+    /// `data` can be a [`Data`], an `[u8; N]` for `N` up to 8, or anything
+    /// else that implements `Into<Data>`. Use `Data::try_from(slice)` first
+    /// to build a frame from a runtime-length `&[u8]`.
+    fn new(id: Id, data: impl Into<Data>) -> Result<Self, ()>;
 
     /// Creates a new remote frame (RTR bit set).
     /// Returns an error when the the identifier is  or the data length code (DLC) not valid.
@@ -47,6 +253,19 @@ pub trait Frame: Sized {
         !self.is_remote_frame()
     }
 
+    /// Returns true if this frame is an error frame.
+    ///
+    /// An error frame is generated by a controller that detects a bus error
+    /// and carries no usable identifier or payload; implementations should
+    /// ignore [`Frame::id`], [`Frame::dlc`] and [`Frame::data`] when this
+    /// returns `true`.
+    ///
+    /// The default implementation returns `false`, for controllers that
+    /// cannot distinguish error frames from other frames.
+    fn is_error_frame(&self) -> bool {
+        false
+    }
+
     /// Returns the frame identifier.
     fn id(&self) -> Id;
 
@@ -57,7 +276,7 @@ pub trait Frame: Sized {
     fn dlc(&self) -> usize;
 
     /// Returns the frame data (0..8 bytes in length).
-    fn data(&self) -> &[u8];
+    fn data(&self) -> &Data;
 }
 
 /// A CAN interface that is able to transmit and receive frames.
@@ -79,6 +298,198 @@ pub trait Can {
     fn receive(&mut self) -> nb::Result<Self::Frame, Self::Error>;
 }
 
+/// Data lengths valid for a CAN FD frame.
+///
+/// Unlike classic CAN, payload lengths above 8 bytes are not contiguous:
+/// the DLC field encodes 0 through 8 directly, then steps up to 12, 16, 20,
+/// 24, 32, 48 and 64.
+pub const FD_DLC: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// A CAN FD Frame
+pub trait FdFrame: Sized {
+    /// Creates a new FD frame.
+    ///
+    /// Returns an error when `data` is longer than 64 bytes or its length is
+    /// not one of the legal FD data length codes (0..=8, 12, 16, 20, 24, 32,
+    /// 48, 64).
+    fn new_fd(id: Id, data: &[u8]) -> Result<Self, ()>;
+
+    /// Returns true if this frame is a extended frame.
+    fn is_extended(&self) -> bool;
+
+    /// Returns true if this frame is a standard frame.
+    fn is_standard(&self) -> bool {
+        !self.is_extended()
+    }
+
+    /// Returns true if this frame is a remote frame.
+    ///
+    /// CAN FD has no remote frames, so this always returns `false`.
+    fn is_remote_frame(&self) -> bool {
+        false
+    }
+
+    /// Returns true if this frame is a data frame.
+    fn is_data_frame(&self) -> bool {
+        !self.is_remote_frame()
+    }
+
+    /// Returns true if the bit rate switch (BRS) flag is set.
+    ///
+    /// When set, the data phase of the frame is transmitted at a higher bit
+    /// rate than the arbitration phase.
+    fn is_brs(&self) -> bool;
+
+    /// Returns true if the error state indicator (ESI) flag is set.
+    ///
+    /// The transmitter sets this flag when it is in the error-passive state.
+    fn is_esi(&self) -> bool;
+
+    /// Returns the frame identifier.
+    fn id(&self) -> Id;
+
+    /// Returns the data length code (DLC).
+    ///
+    /// The DLC always matches the length of the data, see [`FdFrame::data`].
+    fn dlc(&self) -> usize;
+
+    /// Returns the frame data (0..64 bytes in length).
+    fn data(&self) -> &[u8];
+}
+
+/// A CAN FD interface that is able to transmit and receive frames.
+pub trait CanFd {
+    /// Associated frame type.
+    type Frame: FdFrame;
+
+    /// Associated error type.
+    type Error;
+
+    /// Puts a frame in the transmit buffer.
+    ///
+    /// If the buffer is full, this function will try to replace a lower priority frame
+    /// and return it. This is to avoid the priority inversion problem.
+    /// Transmits frames of equal identifier in FIFO fashion.
+    fn transmit(&mut self, frame: &Self::Frame) -> nb::Result<Option<Self::Frame>, Self::Error>;
+
+    /// Returns a received frame if available.
+    fn receive(&mut self) -> nb::Result<Self::Frame, Self::Error>;
+}
+
+/// Wraps an [`FdFrame`] implementation so it also satisfies the classic
+/// [`Frame`] trait.
+///
+/// Every legal FD data length below 9 bytes is also a legal classic DLC, and
+/// CAN FD has no remote frames, so an FD frame can always stand in for a
+/// classic data frame. This lets a single FD-capable controller implement
+/// both [`Can`] and [`CanFd`]: build `Self::Frame` through [`FdFrame`] and
+/// hand out `Classic<Self::Frame>` wherever a [`Frame`] is expected.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Classic<F> {
+    frame: F,
+    data: Data,
+}
+
+impl<F: FdFrame> Classic<F> {
+    /// Wraps an FD frame for use as a classic frame.
+    ///
+    /// Returns an error when the FD frame's data is longer than 8 bytes.
+    pub fn new(frame: F) -> Result<Self, ()> {
+        let data = Data::try_from(frame.data())?;
+        Ok(Self { frame, data })
+    }
+
+    /// Unwraps the underlying FD frame.
+    pub fn into_inner(self) -> F {
+        self.frame
+    }
+}
+
+impl<F: FdFrame> Frame for Classic<F> {
+    fn new(id: Id, data: impl Into<Data>) -> Result<Self, ()> {
+        let data = data.into();
+        let frame = F::new_fd(id, &data)?;
+        Ok(Self { frame, data })
+    }
+
+    fn new_remote(_id: Id, _dlc: usize) -> Result<Self, ()> {
+        // CAN FD has no remote frames, so there is no FD frame that could
+        // faithfully represent one; fabricating a zero-length data frame
+        // would make `is_remote_frame()`/`dlc()` lie about the result.
+        Err(())
+    }
+
+    fn is_extended(&self) -> bool {
+        self.frame.is_extended()
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        false
+    }
+
+    fn id(&self) -> Id {
+        self.frame.id()
+    }
+
+    fn dlc(&self) -> usize {
+        self.frame.dlc()
+    }
+
+    fn data(&self) -> &Data {
+        &self.data
+    }
+}
+
+/// Fault-confinement state of a CAN controller.
+///
+/// A controller moves through these states by counting transmit/receive
+/// errors; see [`BusMonitor::error_counters`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BusState {
+    /// The controller takes part in bus communication and actively signals
+    /// errors it detects.
+    ErrorActive,
+
+    /// The controller still takes part in bus communication, but signals
+    /// errors passively so as to not disturb the bus further.
+    ErrorPassive,
+
+    /// The controller has disconnected itself from the bus after detecting
+    /// too many errors.
+    ///
+    /// Recovery requires the controller to observe 128 occurrences of 11
+    /// consecutive recessive bits before it can rejoin the bus.
+    BusOff,
+}
+
+/// Transmit and receive error counters of a CAN controller.
+///
+/// The counters drive the [`BusState`] transitions: a controller becomes
+/// error-passive when a counter exceeds 127, and bus-off when the transmit
+/// error counter exceeds 255. That value does not fit in these 8 bit
+/// fields, so bus-off shows in [`BusMonitor::bus_state`], not here.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ErrorCounters {
+    /// Transmit error counter (TEC).
+    pub transmit: u8,
+
+    /// Receive error counter (REC).
+    pub receive: u8,
+}
+
+/// A [`Can`] interface that exposes fault-confinement state.
+///
+/// Implement this in addition to [`Can`] for controllers that can report
+/// error frames and error counters, so applications can detect and react to
+/// bus degradation instead of silently losing frames.
+pub trait BusMonitor: Can {
+    /// Returns the controller's current fault-confinement state.
+    fn bus_state(&self) -> BusState;
+
+    /// Returns the controller's transmit and receive error counters.
+    fn error_counters(&self) -> ErrorCounters;
+}
+
 /// Filter mask type.
 pub enum MaskType {
     /// Each filter of the group has an individual mask.
@@ -134,26 +545,57 @@ pub trait FilterGroup {
 }
 
 /// CAN filter interface
+///
+/// A filter's frame format (standard or extended) is derived from the `Id`
+/// it was created with and is part of the match: a filter built from a
+/// `StandardId` does not accept extended frames and vice versa, even if
+/// their base identifiers coincide, unless the format bit is cleared with
+/// [`Filter::with_id_format_mask`]. [`Filter::accept_all`] is the only
+/// constructor that is format-agnostic.
 pub trait Filter {
     /// Creates a filter that accepts all frames.
     fn accept_all() -> Self;
 
     /// Creates a filter that accepts frames with the specified identifier.
+    ///
+    /// The filter's frame format is derived from the `id` variant: a
+    /// `Id::Standard` filter only matches standard frames and a
+    /// `Id::Extended` filter only matches extended frames.
     fn new(id: Id) -> Self;
 
     /// Applies a mask to the filter.
     ///
+    /// The frame format set by [`Filter::new`] is not affected by this mask;
+    /// use [`Filter::with_id_format_mask`] to also mask the frame format.
+    ///
     /// # Example
     ///
     ///    Filter ID:  0b100110111
     ///    Mask:       0b000001111
-    ///    
+    ///
     ///    Receive ID: 0b100110011
     ///                        \----> Not accepted (bit 3 did not match)
-    ///    
+    ///
     ///    Receive ID: 0b000000111 -> accepted
     fn with_mask(&mut self, mask: u32) -> &mut Self;
 
+    /// Applies a mask to the filter, additionally masking the frame format
+    /// (IDE) bit.
+    ///
+    /// Behaves like [`Filter::with_mask`], except that bit 29 of `mask`
+    /// controls whether the frame format set by [`Filter::new`] must also
+    /// match: `1` keeps the strict, format-specific behavior of
+    /// [`Filter::with_mask`]; `0` relaxes the filter so it accepts both
+    /// standard and extended frames sharing the matched base identifier.
+    ///
+    /// The default implementation ignores the format bit and simply
+    /// forwards to [`Filter::with_mask`], i.e. it keeps the strict,
+    /// format-specific behavior. Implementations backed by a controller
+    /// with a configurable IDE filter bit should override this.
+    fn with_id_format_mask(&mut self, mask: u32) -> &mut Self {
+        self.with_mask(mask)
+    }
+
     /// Makes the filter acccept both data and remote frames.
     ///
     /// Sets the RTR bit in the filter mask.
@@ -188,3 +630,207 @@ pub trait FilteredReceiver: Can {
     /// Clears all filters. No messages can be received anymore.
     fn clear_filters(&mut self);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lower_standard_id_has_higher_priority() {
+        let low: Id = StandardId::new(0).unwrap().into();
+        let high: Id = StandardId::new(1).unwrap().into();
+        assert!(low < high);
+    }
+
+    #[test]
+    fn lower_extended_id_has_higher_priority() {
+        let low: Id = ExtendedId::new(0).unwrap().into();
+        let high: Id = ExtendedId::new(1).unwrap().into();
+        assert!(low < high);
+    }
+
+    #[test]
+    fn standard_wins_arbitration_against_extended_sharing_base() {
+        // 0x7FF << 18, the extended identifier whose base matches
+        // `StandardId::MAX`.
+        let standard: Id = StandardId::MAX.into();
+        let extended: Id = ExtendedId::new(0x7FF << 18).unwrap().into();
+        assert_eq!(standard.cmp(&extended), Ordering::Less);
+        assert_eq!(extended.cmp(&standard), Ordering::Greater);
+        assert!(standard < extended);
+    }
+
+    #[test]
+    fn extended_ids_with_equal_base_order_by_full_value() {
+        let base = 0x123 << 18;
+        let low: Id = ExtendedId::new(base).unwrap().into();
+        let high: Id = ExtendedId::new(base + 1).unwrap().into();
+        assert_eq!(low.cmp(&high), Ordering::Less);
+    }
+
+    #[test]
+    fn base_identifier_dominates_over_format_tiebreak() {
+        // A standard frame only wins the tiebreak when the base identifiers
+        // are equal; a lower extended base still outranks a higher standard
+        // identifier.
+        let standard: Id = StandardId::new(1).unwrap().into();
+        let extended: Id = ExtendedId::new(0).unwrap().into();
+        assert!(extended < standard);
+    }
+
+    #[test]
+    fn data_try_from_accepts_up_to_eight_bytes() {
+        let data = Data::try_from(&[1, 2, 3, 4, 5, 6, 7, 8][..]).unwrap();
+        assert_eq!(&*data, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn data_try_from_rejects_nine_bytes() {
+        assert_eq!(Data::try_from(&[0u8; 9][..]), Err(()));
+    }
+
+    #[test]
+    fn data_try_from_does_not_carry_over_trailing_garbage() {
+        // A short payload must not expose the unused tail of the internal
+        // 8 byte buffer.
+        let data = Data::try_from(&[1, 2, 3][..]).unwrap();
+        assert_eq!(&*data, &[1, 2, 3]);
+        assert_eq!(data.len(), 3);
+    }
+
+    #[test]
+    fn data_empty_has_zero_length() {
+        assert_eq!(&*Data::empty(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn data_from_array_matches_try_from_equivalent_slice() {
+        let from_array: Data = [1, 2, 3, 4].into();
+        let from_slice = Data::try_from(&[1, 2, 3, 4][..]).unwrap();
+        assert_eq!(from_array, from_slice);
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct MockFdFrame {
+        id: Id,
+        data: [u8; 64],
+        len: usize,
+    }
+
+    impl FdFrame for MockFdFrame {
+        fn new_fd(id: Id, data: &[u8]) -> Result<Self, ()> {
+            if data.len() > 64 || !FD_DLC.contains(&data.len()) {
+                return Err(());
+            }
+            let mut bytes = [0; 64];
+            bytes[..data.len()].copy_from_slice(data);
+            Ok(Self {
+                id,
+                data: bytes,
+                len: data.len(),
+            })
+        }
+
+        fn is_extended(&self) -> bool {
+            matches!(self.id, Id::Extended(_))
+        }
+
+        fn is_brs(&self) -> bool {
+            false
+        }
+
+        fn is_esi(&self) -> bool {
+            false
+        }
+
+        fn id(&self) -> Id {
+            self.id
+        }
+
+        fn dlc(&self) -> usize {
+            self.len
+        }
+
+        fn data(&self) -> &[u8] {
+            &self.data[..self.len]
+        }
+    }
+
+    fn mock_id() -> Id {
+        StandardId::new(0x42).unwrap().into()
+    }
+
+    #[test]
+    fn classic_new_accepts_fd_frame_within_classic_length() {
+        let fd_frame = MockFdFrame::new_fd(mock_id(), &[1, 2, 3]).unwrap();
+        let classic = Classic::new(fd_frame).unwrap();
+        assert_eq!(&**classic.data(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn classic_new_rejects_fd_frame_longer_than_classic_length() {
+        // 12 is a legal FD DLC but not a legal classic one.
+        let fd_frame = MockFdFrame::new_fd(mock_id(), &[0; 12]).unwrap();
+        assert_eq!(Classic::new(fd_frame), Err(()));
+    }
+
+    #[test]
+    fn classic_frame_new_remote_always_errors() {
+        // CAN FD has no remote frames, so `Classic` must reject this rather
+        // than fabricate a 0-length data frame (regression test for e19c0cf).
+        let result = <Classic<MockFdFrame> as Frame>::new_remote(mock_id(), 8);
+        assert_eq!(result, Err(()));
+    }
+
+    #[test]
+    fn classic_frame_delegates_to_inner_fd_frame() {
+        let classic = <Classic<MockFdFrame> as Frame>::new(mock_id(), [1, 2, 3, 4]).unwrap();
+
+        // Regression test for ef83ddd: the default `Frame::is_error_frame`
+        // must still return `false` for `Classic`, not panic or diverge.
+        assert!(!classic.is_error_frame());
+        assert!(!classic.is_remote_frame());
+        assert_eq!(classic.dlc(), 4);
+        assert_eq!(&**classic.data(), &[1, 2, 3, 4]);
+        assert_eq!(classic.id(), mock_id());
+    }
+
+    #[derive(Debug, Default)]
+    struct MockFilter {
+        last_mask: Option<u32>,
+    }
+
+    impl Filter for MockFilter {
+        fn accept_all() -> Self {
+            Self::default()
+        }
+
+        fn new(_id: Id) -> Self {
+            Self::default()
+        }
+
+        fn with_mask(&mut self, mask: u32) -> &mut Self {
+            self.last_mask = Some(mask);
+            self
+        }
+
+        fn allow_remote(&mut self) -> &mut Self {
+            self
+        }
+
+        fn remote_only(&mut self) -> &mut Self {
+            self
+        }
+    }
+
+    #[test]
+    fn filter_with_id_format_mask_default_forwards_to_with_mask() {
+        let mut filter = MockFilter::accept_all();
+        filter.with_id_format_mask(0x1234);
+
+        // Guards the documented default behavior: an implementation that
+        // does not override `with_id_format_mask` must keep masking only
+        // the identifier, not the frame format bit.
+        assert_eq!(filter.last_mask, Some(0x1234));
+    }
+}